@@ -7,6 +7,7 @@ use pyth_sdk::{Price, PriceFeed, PriceIdentifier};
 use serde::{Deserialize, Serialize};
 use wormhole_sdk::Chain;
 
+pub mod p2w;
 mod serde_hex;
 
 pub type Slot = u64;
@@ -50,6 +51,35 @@ impl From<PriceIdInput> for PriceIdentifier {
 
 type Base64String = String;
 
+/// The input to Hermes' CCIP read endpoint: a price feed id concatenated with a big-endian
+/// publish timestamp, hex-encoded as a single 40-byte blob.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GetVaaCcipInput {
+    #[serde(with = "crate::serde_hex")]
+    pub data: [u8; 40],
+}
+
+impl GetVaaCcipInput {
+    /// Splits the input blob into its constituent price feed id and publish timestamp.
+    pub fn price_id_and_timestamp(&self) -> (PriceIdentifier, UnixTimestamp) {
+        let mut price_id = [0u8; 32];
+        price_id.copy_from_slice(&self.data[..32]);
+
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(&self.data[32..]);
+
+        (
+            PriceIdentifier::new(price_id),
+            UnixTimestamp::from_be_bytes(timestamp),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetVaaCcipResponse {
+    pub data: Base64String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcPriceFeedMetadata {
     pub slot: Option<Slot>,
@@ -80,12 +110,10 @@ pub struct RpcPriceFeed {
 }
 
 impl RpcPriceFeed {
-    // TODO: Use a Encoding type to have None, Base64, and Hex variants instead of binary flag.
-    // TODO: Use a Verbosity type to define None, or Full instead of verbose flag.
     pub fn from_price_feed_update(
         price_feed_update: PriceFeedUpdate,
-        verbose: bool,
-        binary: bool,
+        verbosity: Verbosity,
+        encoding: EncodingType,
     ) -> Self {
         let price_feed = price_feed_update.price_feed;
 
@@ -103,22 +131,35 @@ impl RpcPriceFeed {
                 expo: price_feed.get_ema_price_unchecked().expo,
                 publish_time: price_feed.get_ema_price_unchecked().publish_time,
             },
-            metadata: verbose.then_some(RpcPriceFeedMetadata {
-                emitter_chain: Chain::Pythnet.into(),
-                price_service_receive_time: price_feed_update.received_at,
-                slot: price_feed_update.slot,
-                prev_publish_time: price_feed_update.prev_publish_time,
-            }),
-            vaa: match binary {
-                false => None,
-                true => price_feed_update
+            metadata: match verbosity {
+                Verbosity::None => None,
+                Verbosity::Full => Some(RpcPriceFeedMetadata {
+                    emitter_chain: Chain::Pythnet.into(),
+                    price_service_receive_time: price_feed_update.received_at,
+                    slot: price_feed_update.slot,
+                    prev_publish_time: price_feed_update.prev_publish_time,
+                }),
+            },
+            vaa: match encoding {
+                EncodingType::None => None,
+                _ => price_feed_update
                     .update_data
-                    .map(|data| base64_standard_engine.encode(data)),
+                    .map(|data| encoding.encode_str(&data)),
             },
         }
     }
 }
 
+/// Controls how much detail `RpcPriceFeed::from_price_feed_update` includes in the response.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub enum Verbosity {
+    #[default]
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "full")]
+    Full,
+}
+
 /// A price with a degree of uncertainty at a certain time, represented as a price +- a confidence
 /// interval.
 ///
@@ -198,6 +239,8 @@ pub enum EncodingType {
     Hex,
     #[serde(rename = "base64")]
     Base64,
+    #[serde(rename = "none")]
+    None,
 }
 
 impl EncodingType {
@@ -205,6 +248,17 @@ impl EncodingType {
         match self {
             EncodingType::Base64 => base64_standard_engine.encode(data),
             EncodingType::Hex => hex::encode(data),
+            EncodingType::None => String::new(),
+        }
+    }
+
+    pub fn decode_str(&self, s: &str) -> anyhow::Result<Vec<u8>> {
+        match self {
+            EncodingType::Hex => Ok(hex::decode(s)?),
+            EncodingType::Base64 => Ok(base64_standard_engine.decode(s)?),
+            EncodingType::None => Err(anyhow::anyhow!(
+                "cannot decode data encoded with EncodingType::None"
+            )),
         }
     }
 }
@@ -257,6 +311,26 @@ pub struct PriceUpdate {
     pub parsed: Option<Vec<ParsedPriceUpdate>>,
 }
 
+/// The maximum stake a single publisher may have counted towards the oracle integrity staking
+/// program, as of the enclosing `PublisherStakeCaps`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublisherStakeCap {
+    pub publisher: String,
+    pub cap: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublisherStakeCaps {
+    pub caps: Vec<PublisherStakeCap>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublisherStakeCapsUpdate {
+    pub binary: BinaryPriceUpdate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<Vec<PublisherStakeCaps>>,
+}
+
 impl TryFrom<PriceUpdate> for PriceFeedsWithUpdateData {
     type Error = anyhow::Error;
 
@@ -295,8 +369,8 @@ impl TryFrom<PriceUpdate> for PriceFeedsWithUpdateData {
             .binary
             .data
             .iter()
-            .map(|hex_str| hex::decode(hex_str).unwrap_or_default())
-            .collect::<Vec<Vec<u8>>>();
+            .map(|data_str| price_update.binary.encoding.decode_str(data_str))
+            .collect::<anyhow::Result<Vec<Vec<u8>>>>()?;
 
         Ok(PriceFeedsWithUpdateData {
             price_feeds,
@@ -313,6 +387,31 @@ pub struct PriceFeedMetadata {
     pub attributes: BTreeMap<String, String>,
 }
 
+impl PriceFeedMetadata {
+    /// Parses the `asset_type` attribute into an `AssetType`, if present and recognized.
+    pub fn asset_type(&self) -> Option<AssetType> {
+        self.attributes.get("asset_type")?.parse().ok()
+    }
+
+    /// Returns true when `asset_type` (if given) matches this feed's asset type, and `query`
+    /// (if given) appears as a substring of some attribute value (e.g. the symbol).
+    pub fn matches(&self, asset_type: Option<&AssetType>, query: Option<&str>) -> bool {
+        let asset_type_matches = asset_type
+            .map(|expected| self.asset_type().as_ref() == Some(expected))
+            .unwrap_or(true);
+
+        let query_matches = query
+            .map(|query| {
+                self.attributes
+                    .values()
+                    .any(|value| value.to_lowercase().contains(&query.to_lowercase()))
+            })
+            .unwrap_or(true);
+
+        asset_type_matches && query_matches
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum AssetType {
@@ -329,6 +428,21 @@ impl std::fmt::Display for AssetType {
     }
 }
 
+impl std::str::FromStr for AssetType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "crypto" => Ok(AssetType::Crypto),
+            "fx" => Ok(AssetType::FX),
+            "equity" => Ok(AssetType::Equity),
+            "metals" => Ok(AssetType::Metals),
+            "rates" => Ok(AssetType::Rates),
+            _ => Err(anyhow::anyhow!("unknown asset type: {s}")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamResponse {
     pub data: PriceUpdate,
@@ -0,0 +1,164 @@
+//! Decodes the Pyth-to-Wormhole (P2W) batch price attestation payload — the custom
+//! serialization format Pyth publishes into Wormhole VAAs — into [`PriceFeedUpdate`]s.
+//!
+//! Wire format:
+//! * a 4-byte magic, `b"P2WH"`
+//! * a big-endian `u16` format version (only `2` is supported)
+//! * a 1-byte payload id (`2` is a price batch attestation)
+//! * a big-endian `u16` attestation count and `u16` per-attestation size
+//! * that many fixed-size attestation records
+
+use pyth_sdk::{Price, PriceFeed, PriceIdentifier};
+
+use crate::{PriceFeedUpdate, UnixTimestamp};
+
+const MAGIC: [u8; 4] = *b"P2WH";
+const FORMAT_VERSION: u16 = 2;
+const PAYLOAD_ID_PRICE_BATCH: u8 = 2;
+
+const PRODUCT_ID_SIZE: usize = 32;
+const PRICE_ID_SIZE: usize = 32;
+const ATTESTATION_SIZE: usize = PRODUCT_ID_SIZE
+    + PRICE_ID_SIZE
+    + 8 // price
+    + 8 // conf
+    + 4 // expo
+    + 8 // ema price
+    + 8 // ema conf
+    + 1 // status
+    + 8 // publish_time
+    + 8; // prev_publish_time
+
+/// An error produced while decoding a P2W batch attestation payload.
+#[derive(Debug, PartialEq, Eq)]
+pub enum P2wParseError {
+    BadMagic,
+    UnsupportedVersion(u16),
+    UnknownPayloadId(u8),
+    TruncatedBuffer,
+    UnexpectedTrailingBytes(usize),
+    InvalidAttestationSize { expected: usize, actual: usize },
+}
+
+impl std::fmt::Display for P2wParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            P2wParseError::BadMagic => write!(f, "invalid P2W magic bytes"),
+            P2wParseError::UnsupportedVersion(version) => {
+                write!(f, "unsupported P2W format version: {version}")
+            }
+            P2wParseError::UnknownPayloadId(id) => write!(f, "unknown P2W payload id: {id}"),
+            P2wParseError::TruncatedBuffer => write!(f, "truncated P2W buffer"),
+            P2wParseError::UnexpectedTrailingBytes(count) => {
+                write!(f, "{count} unexpected trailing bytes in P2W buffer")
+            }
+            P2wParseError::InvalidAttestationSize { expected, actual } => write!(
+                f,
+                "unexpected P2W attestation size: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for P2wParseError {}
+
+/// Decodes a P2W batch price attestation payload into a [`PriceFeedUpdate`] per attestation.
+pub fn parse_batch_attestation(data: &[u8]) -> Result<Vec<PriceFeedUpdate>, P2wParseError> {
+    let mut cursor = data;
+
+    if take(&mut cursor, MAGIC.len())? != MAGIC {
+        return Err(P2wParseError::BadMagic);
+    }
+
+    let version = read_u16(&mut cursor)?;
+    if version != FORMAT_VERSION {
+        return Err(P2wParseError::UnsupportedVersion(version));
+    }
+
+    let payload_id = take(&mut cursor, 1)?[0];
+    if payload_id != PAYLOAD_ID_PRICE_BATCH {
+        return Err(P2wParseError::UnknownPayloadId(payload_id));
+    }
+
+    let attestation_count = read_u16(&mut cursor)? as usize;
+    let attestation_size = read_u16(&mut cursor)? as usize;
+    if attestation_size != ATTESTATION_SIZE {
+        return Err(P2wParseError::InvalidAttestationSize {
+            expected: ATTESTATION_SIZE,
+            actual: attestation_size,
+        });
+    }
+
+    let price_feed_updates = (0..attestation_count)
+        .map(|_| parse_attestation(&mut cursor))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !cursor.is_empty() {
+        return Err(P2wParseError::UnexpectedTrailingBytes(cursor.len()));
+    }
+
+    Ok(price_feed_updates)
+}
+
+fn parse_attestation(cursor: &mut &[u8]) -> Result<PriceFeedUpdate, P2wParseError> {
+    let _product_id = take(cursor, PRODUCT_ID_SIZE)?;
+    let price_id: [u8; PRICE_ID_SIZE] = take(cursor, PRICE_ID_SIZE)?.try_into().unwrap();
+
+    let price = read_i64(cursor)?;
+    let conf = read_u64(cursor)?;
+    let expo = read_i32(cursor)?;
+    let ema_price = read_i64(cursor)?;
+    let ema_conf = read_u64(cursor)?;
+    let _status = take(cursor, 1)?[0];
+    let publish_time: UnixTimestamp = read_i64(cursor)?;
+    let prev_publish_time: UnixTimestamp = read_i64(cursor)?;
+
+    let price_feed = PriceFeed::new(
+        PriceIdentifier::new(price_id),
+        Price {
+            price,
+            conf,
+            expo,
+            publish_time,
+        },
+        Price {
+            price: ema_price,
+            conf: ema_conf,
+            expo,
+            publish_time,
+        },
+    );
+
+    Ok(PriceFeedUpdate {
+        price_feed,
+        slot: None,
+        received_at: None,
+        update_data: None,
+        prev_publish_time: Some(prev_publish_time),
+    })
+}
+
+fn take<'a>(cursor: &mut &'a [u8], len: usize) -> Result<&'a [u8], P2wParseError> {
+    if cursor.len() < len {
+        return Err(P2wParseError::TruncatedBuffer);
+    }
+    let (head, tail) = cursor.split_at(len);
+    *cursor = tail;
+    Ok(head)
+}
+
+fn read_u16(cursor: &mut &[u8]) -> Result<u16, P2wParseError> {
+    Ok(u16::from_be_bytes(take(cursor, 2)?.try_into().unwrap()))
+}
+
+fn read_i32(cursor: &mut &[u8]) -> Result<i32, P2wParseError> {
+    Ok(i32::from_be_bytes(take(cursor, 4)?.try_into().unwrap()))
+}
+
+fn read_u64(cursor: &mut &[u8]) -> Result<u64, P2wParseError> {
+    Ok(u64::from_be_bytes(take(cursor, 8)?.try_into().unwrap()))
+}
+
+fn read_i64(cursor: &mut &[u8]) -> Result<i64, P2wParseError> {
+    Ok(i64::from_be_bytes(take(cursor, 8)?.try_into().unwrap()))
+}
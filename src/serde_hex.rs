@@ -0,0 +1,42 @@
+//! Serde support for fixed-size byte arrays encoded as hex strings, optionally prefixed with
+//! "0x", mirroring how `PriceIdInput` accepts price feed ids.
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<S, const N: usize>(data: &[u8; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&format!("0x{}", hex::encode(data)))
+    } else {
+        data.serialize(serializer)
+    }
+}
+
+pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let s = String::deserialize(deserializer)?;
+        let stripped = s.strip_prefix("0x").unwrap_or(&s);
+        let bytes = hex::decode(stripped).map_err(D::Error::custom)?;
+        bytes.try_into().map_err(|bytes: Vec<u8>| {
+            D::Error::custom(format!(
+                "invalid length {}, expected {} bytes",
+                bytes.len(),
+                N
+            ))
+        })
+    } else {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        bytes.try_into().map_err(|bytes: Vec<u8>| {
+            D::Error::custom(format!(
+                "invalid length {}, expected {} bytes",
+                bytes.len(),
+                N
+            ))
+        })
+    }
+}